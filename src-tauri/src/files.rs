@@ -1,5 +1,41 @@
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Recognized Markdown file extensions, shared by the CLI argument filter in
+/// `lib.rs` and [`scan_dir`] so both agree on what counts as a document.
+pub const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown", "mdown", "mkd", "mkdown"];
+
+/// Filesystem metadata for a document, returned by [`get_file_metadata`].
+#[derive(Serialize)]
+pub struct FileMetadata {
+    pub size: u64,
+    pub created: Option<u64>,
+    pub modified: Option<u64>,
+    pub accessed: Option<u64>,
+    pub is_symlink: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<String>,
+}
+
+fn unix_epoch_secs(time: std::io::Result<std::time::SystemTime>) -> Option<u64> {
+    time.ok()?.duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs())
+}
+
+/// Format a Unix file mode as an octal string with the owner's permission
+/// triad, e.g. `0644 (rw-)`.
+#[cfg(unix)]
+fn permission_string(mode: u32) -> String {
+    let owner = (mode >> 6) & 0o7;
+    let triad = format!(
+        "{}{}{}",
+        if owner & 0b100 != 0 { "r" } else { "-" },
+        if owner & 0b010 != 0 { "w" } else { "-" },
+        if owner & 0b001 != 0 { "x" } else { "-" },
+    );
+    format!("{:04o} ({})", mode & 0o777, triad)
+}
 
 /// Read a file's contents as a string
 #[tauri::command]
@@ -14,6 +50,73 @@ pub fn read_file(path: String) -> Result<String, String> {
         .map_err(|e| format!("Failed to read file: {}", e))
 }
 
+/// A byte-range slice of a file, returned by [`read_file_chunk`].
+#[derive(Serialize)]
+pub struct FileChunk {
+    pub content: String,
+    pub offset: u64,
+    pub len: u64,
+    pub total_len: u64,
+    pub eof: bool,
+}
+
+/// `true` if `index` does not fall inside a multi-byte UTF-8 character,
+/// mirroring `str::is_char_boundary` for a raw byte slice.
+fn is_utf8_boundary(bytes: &[u8], index: usize) -> bool {
+    index == bytes.len() || (bytes[index] & 0b1100_0000) != 0b1000_0000
+}
+
+/// Read the byte range `[offset, offset + len)` of a file as UTF-8 text, for
+/// progressively loading very large Markdown documents. The range is
+/// snapped inward to the nearest UTF-8 character boundaries so a chunk never
+/// splits a multi-byte character. Runs off the main thread via a blocking
+/// task since large files can take a while to read.
+#[tauri::command]
+pub async fn read_file_chunk(path: String, offset: u64, len: u64) -> Result<FileChunk, String> {
+    tokio::task::spawn_blocking(move || {
+        let file_path = Path::new(&path);
+        let bytes = fs::read(file_path).map_err(|e| format!("Failed to read file: {}", e))?;
+        let total_len = bytes.len() as u64;
+
+        if offset >= total_len {
+            return Ok(FileChunk {
+                content: String::new(),
+                offset,
+                len: 0,
+                total_len,
+                eof: true,
+            });
+        }
+
+        let mut start = offset as usize;
+        while start < bytes.len() && !is_utf8_boundary(&bytes, start) {
+            start += 1;
+        }
+
+        // Compute `end` from the forward-snapped `start`, not the raw
+        // `offset`, so a requested end that lands before `start` (e.g. a
+        // zero-length chunk starting mid-character) can't snap backward past
+        // it and underflow the slice below.
+        let mut end = start.max((offset + len).min(total_len) as usize);
+        while end > start && !is_utf8_boundary(&bytes, end) {
+            end -= 1;
+        }
+
+        let content = String::from_utf8(bytes[start..end].to_vec())
+            .map_err(|e| format!("Failed to decode file chunk: {}", e))?;
+
+        Ok(FileChunk {
+            len: (end - start) as u64,
+            content,
+            offset,
+            total_len,
+            eof: end as u64 >= total_len,
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to read file chunk: {}", e))?
+}
+
 /// Get the filename from a path
 #[tauri::command]
 pub fn get_filename(path: String) -> String {
@@ -23,3 +126,112 @@ pub fn get_filename(path: String) -> String {
         .unwrap_or("Untitled")
         .to_string()
 }
+
+/// Get filesystem metadata for a path, for a document info panel
+#[tauri::command]
+pub fn get_file_metadata(path: String) -> Result<FileMetadata, String> {
+    let file_path = Path::new(&path);
+
+    let metadata = fs::metadata(file_path)
+        .map_err(|e| format!("Failed to read metadata: {}", e))?;
+
+    let is_symlink = fs::symlink_metadata(file_path)
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false);
+
+    #[cfg(unix)]
+    let permissions = {
+        use std::os::unix::fs::PermissionsExt;
+        Some(permission_string(metadata.permissions().mode()))
+    };
+    #[cfg(not(unix))]
+    let permissions = None;
+
+    Ok(FileMetadata {
+        size: metadata.len(),
+        created: unix_epoch_secs(metadata.created()),
+        modified: unix_epoch_secs(metadata.modified()),
+        accessed: unix_epoch_secs(metadata.accessed()),
+        is_symlink,
+        permissions,
+    })
+}
+
+/// A Markdown file discovered by [`scan_dir`].
+#[derive(Serialize)]
+pub struct MarkdownEntry {
+    pub name: String,
+    pub path: String,
+    pub relative_path: String,
+}
+
+/// Scan a directory for Markdown files (by [`MARKDOWN_EXTENSIONS`]), optionally
+/// recursing up to `max_depth` levels, to power a file sidebar.
+#[tauri::command]
+pub fn scan_dir(dir: String, recursive: bool, max_depth: Option<usize>) -> Result<Vec<MarkdownEntry>, String> {
+    let root = Path::new(&dir);
+    if !root.is_dir() {
+        return Err(format!("Not a directory: {}", dir));
+    }
+
+    let mut entries = Vec::new();
+    scan_dir_at(root, root, recursive, max_depth.unwrap_or(usize::MAX), 0, &mut entries)?;
+    Ok(entries)
+}
+
+fn scan_dir_at(
+    root: &Path,
+    dir: &Path,
+    recursive: bool,
+    max_depth: usize,
+    depth: usize,
+    entries: &mut Vec<MarkdownEntry>,
+) -> Result<(), String> {
+    let dir_entries = fs::read_dir(dir).map_err(|e| format!("Failed to read directory: {}", e))?;
+
+    for dir_entry in dir_entries {
+        let dir_entry = dir_entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        let path = dir_entry.path();
+
+        if path.is_dir() {
+            // Don't follow symlinked directories: a self-referential or
+            // cyclic symlink (e.g. `ln -s .. loop`) would otherwise recurse
+            // forever since `max_depth` defaults to `usize::MAX`.
+            let is_symlink = fs::symlink_metadata(&path)
+                .map(|m| m.file_type().is_symlink())
+                .unwrap_or(false);
+            if recursive && !is_symlink && depth < max_depth {
+                scan_dir_at(root, &path, recursive, max_depth, depth + 1, entries)?;
+            }
+            continue;
+        }
+
+        let is_markdown = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|ext| MARKDOWN_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+            .unwrap_or(false);
+        if !is_markdown {
+            continue;
+        }
+
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("Untitled")
+            .to_string();
+        let relative_path = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .to_string();
+
+        entries.push(MarkdownEntry {
+            name,
+            path: path.to_string_lossy().to_string(),
+            relative_path,
+        });
+    }
+
+    Ok(())
+}
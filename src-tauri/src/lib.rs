@@ -1,7 +1,11 @@
 mod files;
+mod protocol;
+mod watcher;
 
-use files::{read_file, get_filename};
-use tauri::{RunEvent, WebviewUrl, WebviewWindowBuilder};
+use files::{read_file, read_file_chunk, get_filename, get_file_metadata, scan_dir, MARKDOWN_EXTENSIONS};
+use protocol::DocumentRoots;
+use watcher::FileWatchers;
+use tauri::{Manager, RunEvent, WebviewUrl, WebviewWindowBuilder, WindowEvent};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
 use std::path::PathBuf;
@@ -12,6 +16,31 @@ static WINDOW_COUNTER: AtomicUsize = AtomicUsize::new(1);
 // Store for CLI file arguments
 struct CliFiles(Mutex<Vec<String>>);
 
+/// Filter a list of CLI arguments down to existing files with a recognized
+/// Markdown extension, canonicalizing each to an absolute path. Shared by the
+/// initial process launch and by single-instance argv forwarding so both
+/// paths agree on what counts as an openable document.
+fn markdown_cli_files(args: Vec<String>) -> Vec<String> {
+    args.into_iter()
+        .filter(|arg| {
+            let path = PathBuf::from(arg);
+            if !path.exists() {
+                return false;
+            }
+            match path.extension().and_then(|e| e.to_str()) {
+                Some(ext) => MARKDOWN_EXTENSIONS.contains(&ext.to_lowercase().as_str()),
+                None => false,
+            }
+        })
+        .map(|arg| {
+            // Convert to absolute path
+            std::fs::canonicalize(&arg)
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or(arg)
+        })
+        .collect()
+}
+
 /// Get files passed via CLI arguments (called once on startup)
 #[tauri::command]
 fn get_cli_files(state: tauri::State<CliFiles>) -> Vec<String> {
@@ -47,13 +76,27 @@ async fn create_window(app: tauri::AppHandle, file_path: Option<String>) -> Resu
         None => "Curio".to_string()
     };
 
-    WebviewWindowBuilder::new(&app, &label, url)
+    let window = WebviewWindowBuilder::new(&app, &label, url)
         .title(&title)
         .inner_size(900.0, 700.0)
         .min_inner_size(400.0, 300.0)
         .build()
         .map_err(|e| format!("Failed to create window: {}", e))?;
 
+    if let Some(path) = &file_path {
+        app.state::<DocumentRoots>().set(&label, path);
+        watcher::watch(&app, &app.state::<FileWatchers>(), &label, path);
+    }
+
+    let close_handle = app.clone();
+    let close_label = label.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::Destroyed = event {
+            close_handle.state::<FileWatchers>().remove(&close_label);
+            close_handle.state::<DocumentRoots>().remove(&close_label);
+        }
+    });
+
     Ok(label)
 }
 
@@ -63,35 +106,38 @@ pub fn run() {
     let args: Vec<String> = std::env::args().skip(1).collect();
 
     // Filter to only existing files with markdown extensions
-    let cli_files: Vec<String> = args
-        .into_iter()
-        .filter(|arg| {
-            let path = PathBuf::from(arg);
-            if !path.exists() {
-                return false;
-            }
-            match path.extension().and_then(|e| e.to_str()) {
-                Some(ext) => matches!(ext.to_lowercase().as_str(),
-                    "md" | "markdown" | "mdown" | "mkd" | "mkdown"),
-                None => false,
-            }
-        })
-        .map(|arg| {
-            // Convert to absolute path
-            std::fs::canonicalize(&arg)
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or(arg)
-        })
-        .collect();
+    let cli_files = markdown_cli_files(args);
 
     let app = tauri::Builder::default()
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            // A second process launch (e.g. double-clicking another .md file)
+            // forwards its argv here instead of spawning a new process, so
+            // run the same filtering used on first launch and open a window
+            // per matching file in the already-running instance.
+            let handle = app.clone();
+            let files = markdown_cli_files(argv.into_iter().skip(1).collect());
+            tauri::async_runtime::spawn(async move {
+                for file_path in files {
+                    let _ = create_window(handle.clone(), Some(file_path)).await;
+                }
+            });
+        }))
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .manage(CliFiles(Mutex::new(cli_files)))
+        .manage(DocumentRoots::new())
+        .manage(FileWatchers::new())
+        .register_uri_scheme_protocol("curio", |ctx, request| {
+            let roots = ctx.app_handle().state::<DocumentRoots>();
+            protocol::handle(&roots, ctx.webview_label(), &request)
+        })
         .invoke_handler(tauri::generate_handler![
             read_file,
+            read_file_chunk,
             get_filename,
+            get_file_metadata,
+            scan_dir,
             create_window,
             get_cli_files
         ])
@@ -107,24 +153,10 @@ pub fn run() {
                         let file_path = path_str.to_string();
                         let handle = app_handle.clone();
 
-                        // Create a new window for this file
+                        // Reuse create_window so asset-root and watcher
+                        // bookkeeping stay in one place.
                         tauri::async_runtime::spawn(async move {
-                            let window_id = WINDOW_COUNTER.fetch_add(1, Ordering::SeqCst);
-                            let label = format!("curio-{}", window_id);
-                            let encoded = urlencoding::encode(&file_path);
-                            let url = WebviewUrl::App(format!("index.html?file={}", encoded).into());
-
-                            let title = PathBuf::from(&file_path)
-                                .file_name()
-                                .and_then(|n| n.to_str())
-                                .unwrap_or("Curio")
-                                .to_string();
-
-                            let _ = WebviewWindowBuilder::new(&handle, &label, url)
-                                .title(&title)
-                                .inner_size(900.0, 700.0)
-                                .min_inner_size(400.0, 300.0)
-                                .build();
+                            let _ = create_window(handle, Some(file_path)).await;
                         });
                     }
                 }
@@ -0,0 +1,185 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::http::{Request, Response, StatusCode};
+
+/// Maps each window label to the directory of the Markdown file it has open,
+/// so the `curio://` protocol can resolve relative asset links without
+/// exposing the rest of the filesystem.
+pub struct DocumentRoots(Mutex<HashMap<String, PathBuf>>);
+
+impl DocumentRoots {
+    pub fn new() -> Self {
+        Self(Mutex::new(HashMap::new()))
+    }
+
+    /// Record the directory of `file_path` as the asset root for `label`.
+    ///
+    /// The directory is canonicalized so the containment check in
+    /// [`handle`] compares two resolved paths — otherwise a symlinked
+    /// document directory (e.g. macOS `/tmp` -> `/private/tmp`) would never
+    /// match the canonicalized candidate path and every asset would 404.
+    pub fn set(&self, label: &str, file_path: &str) {
+        if let Some(dir) = Path::new(file_path).parent() {
+            let resolved = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+            self.0.lock().unwrap().insert(label.to_string(), resolved);
+        }
+    }
+
+    /// Drop the asset root for `label`, typically once its window closes.
+    pub fn remove(&self, label: &str) {
+        self.0.lock().unwrap().remove(label);
+    }
+
+    fn get(&self, label: &str) -> Option<PathBuf> {
+        self.0.lock().unwrap().get(label).cloned()
+    }
+}
+
+/// Infer a MIME type for the `curio://` asset protocol from a file extension.
+fn mime_type_for(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase()
+        .as_str()
+    {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "woff2" => "font/woff2",
+        "woff" => "font/woff",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "pdf" => "application/pdf",
+        "css" => "text/css",
+        "js" => "text/javascript",
+        "json" => "application/json",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Parse a `Range: bytes=start-end` header into an inclusive byte range,
+/// clamped to the resource length. Also handles the RFC 7233 suffix form
+/// `bytes=-N` ("last N bytes"), which video players commonly send to probe
+/// a file's tail. Returns `None` if the header is malformed or describes an
+/// unsatisfiable range.
+fn parse_range(header: &str, len: u64) -> Option<(u64, u64)> {
+    let spec = header.strip_prefix("bytes=")?;
+    let (start_s, end_s) = spec.split_once('-')?;
+
+    if start_s.is_empty() && !end_s.is_empty() {
+        let suffix_len: u64 = end_s.parse().ok()?;
+        if len == 0 || suffix_len == 0 {
+            return None;
+        }
+        return Some((len.saturating_sub(suffix_len), len.saturating_sub(1)));
+    }
+
+    let start: u64 = if start_s.is_empty() {
+        0
+    } else {
+        start_s.parse().ok()?
+    };
+    let end: u64 = if end_s.is_empty() {
+        len.saturating_sub(1)
+    } else {
+        end_s.parse().ok()?
+    };
+
+    if len == 0 || start > end || start >= len {
+        return None;
+    }
+    Some((start, end.min(len.saturating_sub(1))))
+}
+
+fn not_found() -> Response<Cow<'static, [u8]>> {
+    Response::builder()
+        .status(StatusCode::NOT_FOUND)
+        .body(Cow::Borrowed(&[][..]))
+        .unwrap()
+}
+
+/// Serve a `curio://` request for the webview identified by `webview_label`,
+/// resolving the requested path against that window's document directory.
+///
+/// Supports `Range` requests (returning `206 Partial Content`) so large
+/// assets such as embedded video can be streamed, and returns `404` for
+/// missing files or paths that try to escape the document directory.
+pub fn handle(
+    roots: &DocumentRoots,
+    webview_label: &str,
+    request: &Request<Vec<u8>>,
+) -> Response<Cow<'static, [u8]>> {
+    let base_dir = match roots.get(webview_label) {
+        Some(dir) => dir,
+        None => return not_found(),
+    };
+
+    let requested_path = request.uri().path().trim_start_matches('/');
+    let decoded = match urlencoding::decode(requested_path) {
+        Ok(path) => path.into_owned(),
+        Err(_) => return not_found(),
+    };
+
+    let candidate = base_dir.join(decoded);
+    let resolved = match candidate.canonicalize() {
+        Ok(path) => path,
+        Err(_) => return not_found(),
+    };
+    if !resolved.starts_with(&base_dir) {
+        // The request tried to escape the document's directory.
+        return not_found();
+    }
+
+    let data = match fs::read(&resolved) {
+        Ok(bytes) => bytes,
+        Err(_) => return not_found(),
+    };
+    let total_len = data.len() as u64;
+    let mime = mime_type_for(&resolved);
+
+    if let Some(range_header) = request
+        .headers()
+        .get("range")
+        .and_then(|value| value.to_str().ok())
+    {
+        return match parse_range(range_header, total_len) {
+            Some((start, end)) => {
+                let chunk = data[start as usize..=end as usize].to_vec();
+                Response::builder()
+                    .status(StatusCode::PARTIAL_CONTENT)
+                    .header("Content-Type", mime)
+                    .header("Accept-Ranges", "bytes")
+                    .header("Content-Range", format!("bytes {}-{}/{}", start, end, total_len))
+                    .header("Content-Length", chunk.len().to_string())
+                    .body(Cow::Owned(chunk))
+                    .unwrap()
+            }
+            None => Response::builder()
+                .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                .header("Content-Range", format!("bytes */{}", total_len))
+                .body(Cow::Borrowed(&[][..]))
+                .unwrap(),
+        };
+    }
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("Content-Type", mime)
+        .header("Accept-Ranges", "bytes")
+        .header("Content-Length", total_len.to_string())
+        .body(Cow::Owned(data))
+        .unwrap()
+}
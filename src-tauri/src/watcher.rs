@@ -0,0 +1,76 @@
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+/// Active filesystem watchers keyed by window label. Each window that has a
+/// file open owns at most one watcher here, torn down when the window
+/// closes or a new file replaces the one being watched.
+pub struct FileWatchers(Mutex<HashMap<String, RecommendedWatcher>>);
+
+impl FileWatchers {
+    pub fn new() -> Self {
+        Self(Mutex::new(HashMap::new()))
+    }
+
+    /// Stop watching on behalf of `label`, if it has an active watcher.
+    pub fn remove(&self, label: &str) {
+        self.0.lock().unwrap().remove(label);
+    }
+}
+
+/// Payload for the `file-changed` event emitted when a watched document is
+/// modified on disk by an external editor.
+#[derive(Clone, Serialize)]
+struct FileChangedPayload {
+    label: String,
+    path: String,
+}
+
+/// Start watching `path` for external edits, emitting `file-changed` to
+/// `label`'s window whenever the file changes on disk. Replaces any watcher
+/// already registered for `label`.
+///
+/// Watches the file's parent directory rather than the file itself: many
+/// editors (vim, and most "safe write" implementations) save by writing a
+/// temp file and renaming it over the original, which replaces the inode a
+/// direct file watch is tracking and silently stops it from firing again.
+/// Directory events are filtered down to ones naming our path.
+pub fn watch(app: &AppHandle, watchers: &FileWatchers, label: &str, path: &str) {
+    let watched_path = Path::new(path).to_path_buf();
+    let parent = match watched_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => return,
+    };
+
+    let app_handle = app.clone();
+    let window_label = label.to_string();
+    let event_path = watched_path.clone();
+
+    let mut watcher = match notify::recommended_watcher(move |result: notify::Result<Event>| {
+        let Ok(event) = result else { return };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        if !event.paths.iter().any(|changed| changed == &event_path) {
+            return;
+        }
+        let _ = app_handle.emit_to(
+            &window_label,
+            "file-changed",
+            FileChangedPayload {
+                label: window_label.clone(),
+                path: event_path.to_string_lossy().into_owned(),
+            },
+        );
+    }) {
+        Ok(watcher) => watcher,
+        Err(_) => return,
+    };
+
+    if watcher.watch(&parent, RecursiveMode::NonRecursive).is_ok() {
+        watchers.0.lock().unwrap().insert(label.to_string(), watcher);
+    }
+}